@@ -1,9 +1,13 @@
 use std::{
     collections::HashMap,
+    path::PathBuf,
+    process::Stdio,
     sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
 };
 
-use chrono::Local;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 
 use rmcp::{
     RoleServer, ServerHandler,
@@ -18,18 +22,52 @@ use rmcp::{
     service::RequestContext,
     tool, tool_handler, tool_router,
 };
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::{
     fs,
-    io::AsyncWriteExt,
+    io::{AsyncReadExt, AsyncWriteExt},
     process::Command,
     time::{self, Instant},
 };
 
+/// 发送 SIGTERM 后、改发 SIGKILL 前等待子进程退出的宽限期
+const KILL_GRACE_PERIOD: Duration = Duration::from_secs(5);
+/// `get_url` 使用的共享 HTTP 客户端的默认总超时时间
+const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+/// 瞬时错误（5xx / 连接失败）的最大重试次数
+const DEFAULT_MAX_RETRIES: u32 = 3;
+/// 响应正文截断到的最大字节数，避免超大页面撑爆上下文
+const DEFAULT_MAX_RESPONSE_BYTES: usize = 64 * 1024;
+/// 轮询文件系统变化的间隔
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 同一路径的重复 modify 事件在此窗口内会被去重。必须大于 `WATCH_POLL_INTERVAL`：
+/// 每个 tick 最多只产生一次 modify 事件，所以两次检测之间的最小间隔天然就是一个
+/// poll interval，去抖窗口若小于等于它就永远不会生效（覆盖不了跨连续几个 tick
+/// 的重复写入）。
+const WATCH_DEBOUNCE_WINDOW: Duration = Duration::from_millis(900);
+/// 分块大小（按空白切分后的词数），近似 512 token
+const CHUNK_SIZE_WORDS: usize = 512;
+/// 相邻分块的重叠词数，近似 64 token
+const CHUNK_OVERLAP_WORDS: usize = 64;
+/// 向量索引持久化文件的默认路径
+const DEFAULT_INDEX_PATH: &str = "interview_index.json";
+/// 在 `ShellMode::Allowlisted` 下禁止出现的 shell 元字符，防止
+/// `"ls ; rm -rf /tmp/x"` 这类命令绕过对首个可执行名的白名单检查。
+const SHELL_METACHARACTERS: &[char] = &[';', '&', '|', '$', '`', '(', ')', '<', '>', '\n', '\\'];
+/// RRULE 展开的发生次数上限，防止没有 COUNT/UNTIL 的规则生成无穷多个事件
+const RRULE_MAX_OCCURRENCES: usize = 366;
+
 #[derive(Clone)]
 pub struct InterviewTool {
     instant_map: Arc<RwLock<HashMap<String, InstantInfo>>>,
+    process_map: Arc<RwLock<HashMap<String, ProcessInfo>>>,
+    watcher_map: Arc<RwLock<HashMap<String, WatcherInfo>>>,
+    vector_store: Arc<RwLock<Vec<DocChunk>>>,
+    schedule_store: Arc<RwLock<Vec<ScheduledEvent>>>,
+    policy: SecurityPolicy,
+    http_client: reqwest::Client,
     tool_router: ToolRouter<Self>,
     prompt_router: PromptRouter<Self>,
 }
@@ -46,6 +84,257 @@ impl InstantInfo {
     }
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ProcessStatus {
+    Running,
+    Exited { code: Option<i32> },
+}
+
+struct ProcessInfo {
+    cmd: String,
+    pid: u32,
+    stdin: Option<tokio::process::ChildStdin>,
+    stdout_buf: Arc<RwLock<String>>,
+    stderr_buf: Arc<RwLock<String>>,
+    status: Arc<RwLock<ProcessStatus>>,
+}
+
+/// 单个进程的 stdout/stderr 环形缓冲区上限：超出时从头部丢弃最旧的字节，
+/// 避免一个话痨的长跑命令在两次读取之间无限占用内存。
+const PROCESS_OUTPUT_RING_CAPACITY: usize = 64 * 1024;
+
+/// 向环形缓冲区追加内容，超出 `PROCESS_OUTPUT_RING_CAPACITY` 时丢弃最旧的
+/// 字节（在字符边界上截断，避免把多字节 UTF-8 字符切碎）。
+fn ring_push(buf: &mut String, chunk: &str) {
+    buf.push_str(chunk);
+    if buf.len() > PROCESS_OUTPUT_RING_CAPACITY {
+        let excess_start = buf.len() - PROCESS_OUTPUT_RING_CAPACITY;
+        let mut boundary = excess_start;
+        while boundary < buf.len() && !buf.is_char_boundary(boundary) {
+            boundary += 1;
+        }
+        buf.drain(..boundary);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum WatchEventKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WatchEvent {
+    path: PathBuf,
+    kind: WatchEventKind,
+    timestamp: chrono::DateTime<Local>,
+}
+
+/// 单个被监控文件最近一次观测到的 mtime + size，用于判断变化
+#[derive(Clone, PartialEq)]
+struct FileStamp {
+    modified: SystemTime,
+    size: u64,
+}
+
+struct WatcherInfo {
+    path: PathBuf,
+    events: Arc<RwLock<Vec<WatchEvent>>>,
+    stopped: Arc<RwLock<bool>>,
+}
+
+/// 已索引的一段文本及其向量，持久化到 `DEFAULT_INDEX_PATH`。`embedding_source`
+/// 标识这个向量是由哪个 backend 产生的（本地哈希词袋或某个远程 embeddings
+/// 端点），用于在检索时拒绝把不同来源/维度的向量混在一起比较。
+/// `#[serde(default)]` 让加载旧版本（还没有这个字段）持久化的索引文件不会报错。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DocChunk {
+    uri: String,
+    text: String,
+    vector: Vec<f32>,
+    #[serde(default = "DocChunk::unknown_source")]
+    embedding_source: String,
+}
+
+impl DocChunk {
+    fn unknown_source() -> String {
+        "unknown".to_string()
+    }
+}
+
+/// 从 .ics VEVENT 解析出的一个面试时间段
+#[derive(Debug, Clone)]
+struct ScheduledEvent {
+    uid: String,
+    summary: String,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+}
+
+/// `use_cmd` / `spawn_process` 的执行权限模式
+#[derive(Clone)]
+enum ShellMode {
+    /// 完全禁止执行 shell 命令
+    Disabled,
+    /// 只允许命令的首个可执行名在白名单内
+    Allowlisted(Vec<String>),
+    /// 不做限制（与历史行为一致）
+    Unrestricted,
+}
+
+impl ShellMode {
+    fn describe(&self) -> String {
+        match self {
+            ShellMode::Disabled => "disabled".to_string(),
+            ShellMode::Allowlisted(bins) => format!("allowlisted: {}", bins.join(", ")),
+            ShellMode::Unrestricted => "unrestricted".to_string(),
+        }
+    }
+}
+
+/// 在构造时配置好的沙箱策略，`read_file`/`create_file`/`use_cmd`/`spawn_process`
+/// 在执行前都会先经过它的检查。
+#[derive(Clone)]
+struct SecurityPolicy {
+    /// 允许访问的根目录（已规范化），为空表示不限制路径（与历史行为一致）
+    allowed_roots: Vec<PathBuf>,
+    shell_mode: ShellMode,
+    /// 额外的按命令校验的钩子，例如拒绝包含 `rm -rf` 的命令
+    arg_validator: Option<Arc<dyn Fn(&str) -> bool + Send + Sync>>,
+}
+
+impl SecurityPolicy {
+    /// 从环境变量构造默认策略，未配置时等价于历史上的无限制行为：
+    /// `INTERVIEW_ALLOWED_ROOTS`：以 `:` 分隔的允许根目录列表
+    /// `INTERVIEW_SHELL_MODE`：`disabled` | `unrestricted` | `allowlisted:bin1,bin2`
+    fn from_env() -> Self {
+        let allowed_roots = std::env::var("INTERVIEW_ALLOWED_ROOTS")
+            .ok()
+            .map(|roots| {
+                roots
+                    .split(':')
+                    .filter(|s| !s.is_empty())
+                    .filter_map(|root| std::fs::canonicalize(root).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let shell_mode = match std::env::var("INTERVIEW_SHELL_MODE") {
+            Ok(mode) if mode == "disabled" => ShellMode::Disabled,
+            Ok(mode) if mode.starts_with("allowlisted:") => ShellMode::Allowlisted(
+                mode["allowlisted:".len()..]
+                    .split(',')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string())
+                    .collect(),
+            ),
+            _ => ShellMode::Unrestricted,
+        };
+
+        Self {
+            allowed_roots,
+            shell_mode,
+            arg_validator: None,
+        }
+    }
+    /// 尽量规范化路径用于越权检查：存在就直接 canonicalize，否则规范化
+    /// 其存在的父目录再拼回文件名，这样 `create_file` 写新文件时也能被检查到。
+    fn resolve_for_check(path: &str) -> PathBuf {
+        let path = PathBuf::from(path);
+        if let Ok(canonical) = std::fs::canonicalize(&path) {
+            return canonical;
+        }
+        for ancestor in path.ancestors().skip(1) {
+            if let Ok(canonical_ancestor) = std::fs::canonicalize(ancestor) {
+                if let Ok(suffix) = path.strip_prefix(ancestor) {
+                    return canonical_ancestor.join(suffix);
+                }
+            }
+        }
+        path
+    }
+    /// `resolve_for_check` calls `std::fs::canonicalize`, a blocking syscall;
+    /// this is invoked on every `read_file`/`create_file`/`watch_path`/
+    /// `load_schedule` call, so it runs on `spawn_blocking` rather than
+    /// inline on the async task's tokio worker thread.
+    async fn check_path(&self, path: &str) -> Result<(), McpError> {
+        if self.allowed_roots.is_empty() {
+            return Ok(());
+        }
+        let owned_path = path.to_string();
+        let resolved = tokio::task::spawn_blocking(move || Self::resolve_for_check(&owned_path))
+            .await
+            .map_err(|err| {
+                McpError::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("failed to resolve path {}: {}", path, err),
+                    None,
+                )
+            })?;
+        if self.allowed_roots.iter().any(|root| resolved.starts_with(root)) {
+            Ok(())
+        } else {
+            Err(McpError::new(
+                ErrorCode::INVALID_REQUEST,
+                format!(
+                    "policy violation: path {} is outside the allowed roots",
+                    path
+                ),
+                None,
+            ))
+        }
+    }
+    fn check_shell(&self, cmd: &str) -> Result<(), McpError> {
+        match &self.shell_mode {
+            ShellMode::Disabled => {
+                return Err(McpError::new(
+                    ErrorCode::INVALID_REQUEST,
+                    "policy violation: shell execution is disabled",
+                    None,
+                ));
+            }
+            ShellMode::Allowlisted(bins) => {
+                // The command still runs through `sh -c`, so matching only the
+                // first whitespace-separated token is bypassable with `;`, `&&`,
+                // `|`, `$()`, backticks, etc. Reject any shell metacharacters
+                // outright in this mode rather than trying to allowlist them too.
+                if cmd.contains(SHELL_METACHARACTERS) {
+                    return Err(McpError::new(
+                        ErrorCode::INVALID_REQUEST,
+                        format!(
+                            "policy violation: command `{}` contains shell metacharacters, which are not allowed in allowlisted mode",
+                            cmd
+                        ),
+                        None,
+                    ));
+                }
+                let binary = cmd.split_whitespace().next().unwrap_or("");
+                if !bins.iter().any(|b| b == binary) {
+                    return Err(McpError::new(
+                        ErrorCode::INVALID_REQUEST,
+                        format!("policy violation: binary `{}` is not allowlisted", binary),
+                        None,
+                    ));
+                }
+            }
+            ShellMode::Unrestricted => {}
+        }
+        if let Some(validator) = &self.arg_validator {
+            if !validator(cmd) {
+                return Err(McpError::new(
+                    ErrorCode::INVALID_REQUEST,
+                    format!("policy violation: command `{}` rejected by validator", cmd),
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 struct CreateInstantArgs {
     label: String,
@@ -68,6 +357,42 @@ struct CmdArgs {
     cmd: String,
 }
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+struct SpawnProcessArgs {
+    cmd: String,
+}
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+struct ProcessIdArgs {
+    process_id: String,
+}
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+struct WriteProcessStdinArgs {
+    process_id: String,
+    input: String,
+}
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+struct WatchPathArgs {
+    path: String,
+}
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+struct WatchIdArgs {
+    watch_id: String,
+}
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+struct IndexDocumentArgs {
+    uri: String,
+    text: String,
+}
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+struct SearchContextArgs {
+    query: String,
+    #[serde(default)]
+    top_k: Option<usize>,
+}
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+struct LoadScheduleArgs {
+    source: String,
+}
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 struct DirInfo {
     name: DirName,
 }
@@ -80,16 +405,54 @@ enum DirName {
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 struct GetUrlArgs {
     url: String,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    headers: Option<HashMap<String, String>>,
 }
 #[tool_router]
 impl InterviewTool {
     pub fn new() -> Self {
         Self {
             instant_map: Arc::new(RwLock::new(HashMap::new())),
+            process_map: Arc::new(RwLock::new(HashMap::new())),
+            watcher_map: Arc::new(RwLock::new(HashMap::new())),
+            vector_store: Arc::new(RwLock::new(Self::load_vector_store())),
+            schedule_store: Arc::new(RwLock::new(Vec::new())),
+            policy: SecurityPolicy::from_env(),
+            http_client: Self::build_http_client(),
             tool_router: Self::tool_router(),
             prompt_router: Self::prompt_router(),
         }
     }
+    /// 构建 `get_url` 复用的共享客户端。超时时间与可选的 Bearer token
+    /// 通过环境变量 `INTERVIEW_HTTP_TIMEOUT_SECS` / `INTERVIEW_HTTP_BEARER_TOKEN`
+    /// 配置，未设置时使用默认值。TLS 后端目前固定为 reqwest 的默认实现；
+    /// 本仓库尚无 Cargo.toml，native-tls/rustls 之间的 feature 切换还没有接入，
+    /// 需要时应在补齐构建清单时一并添加。
+    fn build_http_client() -> reqwest::Client {
+        let timeout = std::env::var("INTERVIEW_HTTP_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_HTTP_TIMEOUT);
+
+        let mut default_headers = HeaderMap::new();
+        if let Ok(token) = std::env::var("INTERVIEW_HTTP_BEARER_TOKEN") {
+            if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token)) {
+                default_headers.insert(AUTHORIZATION, value);
+            }
+        }
+
+        reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(timeout)
+            .default_headers(default_headers)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new())
+    }
     fn _create_resource_text(&self, uri: &str, name: &str) -> Resource {
         RawResource::new(uri, name.to_string()).no_annotation()
     }
@@ -169,6 +532,7 @@ impl InterviewTool {
         &self,
         Parameters(args): Parameters<ReadFileArgs>,
     ) -> Result<CallToolResult, McpError> {
+        self.policy.check_path(&args.file_path).await?;
         let file_bytes = fs::read(&args.file_path).await.map_err(|err| {
             McpError::new(
                 ErrorCode::RESOURCE_NOT_FOUND,
@@ -193,6 +557,7 @@ impl InterviewTool {
         &self,
         Parameters(args): Parameters<CreateFileArgs>,
     ) -> Result<CallToolResult, McpError> {
+        self.policy.check_path(&args.file_path).await?;
         let mut file = fs::File::create(&args.file_path).await.map_err(|err| {
             McpError::new(
                 ErrorCode::INTERNAL_ERROR,
@@ -217,7 +582,7 @@ impl InterviewTool {
         &self,
         Parameters(args): Parameters<CmdArgs>,
     ) -> Result<CallToolResult, McpError> {
-        
+        self.policy.check_shell(&args.cmd)?;
         let output = Command::new("sh")
             .arg("-c")
             .arg(&args.cmd)
@@ -242,30 +607,1119 @@ impl InterviewTool {
             ))
         }
     }
-    #[tool(description = "通过网络通过Get方法访问url，并且返回内容")]
-    async fn get_url(
+    #[tool(
+        description = "启动一个长期运行的命令（通过 sh -c），返回 process_id，不会阻塞等待命令结束。适合构建/测试等耗时命令。"
+    )]
+    async fn spawn_process(
         &self,
-        Parameters(args): Parameters<GetUrlArgs>,
+        Parameters(args): Parameters<SpawnProcessArgs>,
     ) -> Result<CallToolResult, McpError> {
-        let text = reqwest::get(&args.url)
-            .await
+        self.policy.check_shell(&args.cmd)?;
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&args.cmd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
             .map_err(|err| {
                 McpError::new(
-                    ErrorCode::INVALID_REQUEST,
-                    format!("Failed to get url :{}, error: {}", args.url, err),
+                    ErrorCode::INVALID_PARAMS,
+                    format!("failed to spawn cmd {}, error: {}", args.cmd, err),
+                    None,
+                )
+            })?;
+
+        let stdin = child.stdin.take();
+        let mut stdout = child.stdout.take();
+        let mut stderr = child.stderr.take();
+
+        let stdout_buf = Arc::new(RwLock::new(String::new()));
+        let stderr_buf = Arc::new(RwLock::new(String::new()));
+        let status = Arc::new(RwLock::new(ProcessStatus::Running));
+
+        if let Some(mut stdout) = stdout.take() {
+            let buf = stdout_buf.clone();
+            tokio::spawn(async move {
+                let mut chunk = [0u8; 4096];
+                loop {
+                    match stdout.read(&mut chunk).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            if let Ok(mut guard) = buf.write() {
+                                ring_push(&mut guard, &String::from_utf8_lossy(&chunk[..n]));
+                            }
+                        }
+                    }
+                }
+            });
+        }
+        if let Some(mut stderr) = stderr.take() {
+            let buf = stderr_buf.clone();
+            tokio::spawn(async move {
+                let mut chunk = [0u8; 4096];
+                loop {
+                    match stderr.read(&mut chunk).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            if let Ok(mut guard) = buf.write() {
+                                ring_push(&mut guard, &String::from_utf8_lossy(&chunk[..n]));
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        let process_id = uuid::Uuid::new_v4().to_string();
+        // `child` is moved wholesale into the reaper task below so `wait()`
+        // never has to share ownership (and thus never blocks anyone else)
+        // with `kill_process`, which only needs the pid to send signals.
+        let pid = child.id().ok_or(McpError::new(
+            ErrorCode::INTERNAL_ERROR,
+            "failed to read pid of spawned process",
+            None,
+        ))?;
+        {
+            let status = status.clone();
+            tokio::spawn(async move {
+                let exit = child.wait().await;
+                let code = exit.ok().and_then(|s| s.code());
+                if let Ok(mut guard) = status.write() {
+                    *guard = ProcessStatus::Exited { code };
+                }
+            });
+        }
+
+        let process_map = self.process_map.clone();
+        let mut guard = process_map.write().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired write lock :{}", err),
+                None,
+            )
+        })?;
+        guard.insert(
+            process_id.clone(),
+            ProcessInfo {
+                cmd: args.cmd,
+                pid,
+                stdin,
+                stdout_buf,
+                stderr_buf,
+                status,
+            },
+        );
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "process id is {}",
+            process_id
+        ))]))
+    }
+    #[tool(
+        description = "读取并清空某个进程自上次读取以来累计的 stdout/stderr，同时返回运行状态（running 或 exited(code)）。"
+    )]
+    async fn read_process_output(
+        &self,
+        Parameters(args): Parameters<ProcessIdArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let process_map = self.process_map.clone();
+        let guard = process_map.read().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired read lock :{}", err),
+                None,
+            )
+        })?;
+        let info = guard.get(&args.process_id).ok_or(McpError::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("not found any process through id {}", args.process_id),
+            None,
+        ))?;
+
+        let stdout = std::mem::take(&mut *info.stdout_buf.write().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired write lock :{}", err),
+                None,
+            )
+        })?);
+        let stderr = std::mem::take(&mut *info.stderr_buf.write().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired write lock :{}", err),
+                None,
+            )
+        })?);
+        let status = info
+            .status
+            .read()
+            .map_err(|err| {
+                McpError::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to acquired read lock :{}", err),
                     None,
                 )
             })?
-            .text()
+            .clone();
+        let status_text = match status {
+            ProcessStatus::Running => "running".to_string(),
+            ProcessStatus::Exited { code } => format!("exited({})", code.unwrap_or(-1)),
+        };
+
+        Ok(CallToolResult::success(vec![
+            Content::text(format!("cmd: {}", info.cmd)),
+            Content::text(format!("status: {}", status_text)),
+            Content::text(format!("stdout: {}", stdout)),
+            Content::text(format!("stderr: {}", stderr)),
+        ]))
+    }
+    #[tool(description = "向某个正在运行的进程的 stdin 写入内容。")]
+    async fn write_process_stdin(
+        &self,
+        Parameters(args): Parameters<WriteProcessStdinArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let process_map = self.process_map.clone();
+        let mut guard = process_map.write().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired write lock :{}", err),
+                None,
+            )
+        })?;
+        let info = guard.get_mut(&args.process_id).ok_or(McpError::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("not found any process through id {}", args.process_id),
+            None,
+        ))?;
+        let stdin = info.stdin.as_mut().ok_or(McpError::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("process {} has no open stdin", args.process_id),
+            None,
+        ))?;
+        stdin
+            .write_all(args.input.as_bytes())
             .await
-            .map_err(|_| {
+            .map_err(|err| {
                 McpError::new(
-                    ErrorCode::INVALID_REQUEST,
-                    format!("the repsonse is not String"),
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("failed to write stdin, error: {}", err),
                     None,
                 )
             })?;
-        Ok(CallToolResult::success(vec![Content::text(text)]))
+        Ok(CallToolResult::success(vec![]))
+    }
+    #[tool(
+        description = "结束一个进程：先发送 SIGTERM，等待宽限期后如果仍未退出再发送 SIGKILL。"
+    )]
+    async fn kill_process(
+        &self,
+        Parameters(args): Parameters<ProcessIdArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        // Only the pid and a handle to the status flag are needed here — the
+        // `Child` itself is owned exclusively by the reaper task spawned in
+        // `spawn_process`, so this never has to wait behind its `wait()` call.
+        let (pid, status) = {
+            let process_map = self.process_map.clone();
+            let guard = process_map.read().map_err(|err| {
+                McpError::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to acquired read lock :{}", err),
+                    None,
+                )
+            })?;
+            let info = guard.get(&args.process_id).ok_or(McpError::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("not found any process through id {}", args.process_id),
+                None,
+            ))?;
+            (info.pid, info.status.clone())
+        };
+
+        let is_exited = || {
+            matches!(
+                status.read().as_deref(),
+                Ok(ProcessStatus::Exited { .. })
+            )
+        };
+
+        if !is_exited() {
+            unsafe {
+                libc::kill(pid as i32, libc::SIGTERM);
+            }
+        }
+
+        let exited = time::timeout(KILL_GRACE_PERIOD, async {
+            while !is_exited() {
+                time::sleep(Duration::from_millis(200)).await;
+            }
+        })
+        .await
+        .is_ok();
+
+        if !exited {
+            unsafe {
+                libc::kill(pid as i32, libc::SIGKILL);
+            }
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "process {} killed",
+            args.process_id
+        ))]))
+    }
+    #[tool(
+        description = "注册一个绝对路径（文件或目录）的变化监控，返回 watch_id。内部轮询 mtime/size 记录 created/modified/deleted 事件。"
+    )]
+    async fn watch_path(
+        &self,
+        Parameters(args): Parameters<WatchPathArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        self.policy.check_path(&args.path).await?;
+        let root = PathBuf::from(&args.path);
+        let events = Arc::new(RwLock::new(Vec::new()));
+        let stopped = Arc::new(RwLock::new(false));
+
+        {
+            let root = root.clone();
+            let events = events.clone();
+            let stopped = stopped.clone();
+            tokio::spawn(async move {
+                let mut stamps: HashMap<PathBuf, FileStamp> = Self::snapshot_stamps(&root).await;
+                let mut last_emitted: HashMap<PathBuf, Instant> = HashMap::new();
+                let mut interval = time::interval(WATCH_POLL_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    if stopped.read().map(|s| *s).unwrap_or(true) {
+                        break;
+                    }
+
+                    let current = Self::snapshot_stamps(&root).await;
+
+                    let mut new_events = Vec::new();
+                    for (path, stamp) in &current {
+                        match stamps.get(path) {
+                            None => new_events.push((path.clone(), WatchEventKind::Created)),
+                            Some(prev) if prev != stamp => {
+                                new_events.push((path.clone(), WatchEventKind::Modified))
+                            }
+                            _ => {}
+                        }
+                    }
+                    for path in stamps.keys() {
+                        if !current.contains_key(path) {
+                            new_events.push((path.clone(), WatchEventKind::Deleted));
+                        }
+                    }
+                    stamps = current;
+
+                    if !new_events.is_empty() {
+                        let now = Instant::now();
+                        let mut guard = match events.write() {
+                            Ok(g) => g,
+                            Err(_) => break,
+                        };
+                        for (path, kind) in new_events {
+                            if matches!(kind, WatchEventKind::Modified)
+                                && Self::is_within_debounce_window(
+                                    last_emitted.get(&path).copied(),
+                                    now,
+                                )
+                            {
+                                continue;
+                            }
+                            last_emitted.insert(path.clone(), now);
+                            guard.push(WatchEvent {
+                                path,
+                                kind,
+                                timestamp: Local::now(),
+                            });
+                        }
+                    }
+                }
+            });
+        }
+
+        let watch_id = uuid::Uuid::new_v4().to_string();
+        let watcher_map = self.watcher_map.clone();
+        let mut guard = watcher_map.write().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired write lock :{}", err),
+                None,
+            )
+        })?;
+        guard.insert(
+            watch_id.clone(),
+            WatcherInfo {
+                path: root,
+                events,
+                stopped,
+            },
+        );
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "watch id is {}",
+            watch_id
+        ))]))
+    }
+    /// 判断一次 modify 事件是否落在上一次发出事件的去抖窗口内，应该被丢弃。
+    /// 抽出为独立函数方便单测覆盖去抖窗口本身的生效条件。
+    fn is_within_debounce_window(last_emitted: Option<Instant>, now: Instant) -> bool {
+        match last_emitted {
+            Some(last) => now.duration_since(last) < WATCH_DEBOUNCE_WINDOW,
+            None => false,
+        }
+    }
+    /// 递归遍历 root（文件或目录），返回每个文件当前的 mtime+size 快照
+    async fn snapshot_stamps(root: &PathBuf) -> HashMap<PathBuf, FileStamp> {
+        let mut stamps = HashMap::new();
+        let mut stack = vec![root.clone()];
+        while let Some(path) = stack.pop() {
+            // Use `symlink_metadata` (does not follow symlinks) and never descend
+            // into a symlink: a watched tree containing a symlink cycle would
+            // otherwise make this walk loop/grow without bound on every poll tick.
+            let metadata = match fs::symlink_metadata(&path).await {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            if metadata.is_symlink() {
+                continue;
+            }
+            if metadata.is_dir() {
+                let mut entries = match fs::read_dir(&path).await {
+                    Ok(e) => e,
+                    Err(_) => continue,
+                };
+                while let Ok(Some(entry)) = entries.next_entry().await {
+                    stack.push(entry.path());
+                }
+            } else if let Ok(modified) = metadata.modified() {
+                stamps.insert(
+                    path,
+                    FileStamp {
+                        modified,
+                        size: metadata.len(),
+                    },
+                );
+            }
+        }
+        stamps
+    }
+    #[tool(
+        description = "返回并清空某个 watch_id 自上次轮询以来累计的 create/modify/delete 事件。"
+    )]
+    async fn poll_changes(
+        &self,
+        Parameters(args): Parameters<WatchIdArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let watcher_map = self.watcher_map.clone();
+        let guard = watcher_map.read().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired read lock :{}", err),
+                None,
+            )
+        })?;
+        let info = guard.get(&args.watch_id).ok_or(McpError::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("not found any watcher through id {}", args.watch_id),
+            None,
+        ))?;
+
+        let drained = std::mem::take(&mut *info.events.write().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired write lock :{}", err),
+                None,
+            )
+        })?);
+
+        if drained.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "no changes since last poll",
+            )]));
+        }
+
+        let lines = drained
+            .into_iter()
+            .map(|e| {
+                format!(
+                    "{:?} {} at {}",
+                    e.kind,
+                    e.path.display(),
+                    e.timestamp.format("%Y-%m-%d %H:%M:%S%.3f")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Ok(CallToolResult::success(vec![Content::text(lines)]))
+    }
+    #[tool(description = "停止一个 watch_id 对应的监控并释放资源。")]
+    async fn unwatch_path(
+        &self,
+        Parameters(args): Parameters<WatchIdArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let watcher_map = self.watcher_map.clone();
+        let mut guard = watcher_map.write().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired write lock :{}", err),
+                None,
+            )
+        })?;
+        let info = guard.remove(&args.watch_id).ok_or(McpError::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("not found any watcher through id {}", args.watch_id),
+            None,
+        ))?;
+        if let Ok(mut stopped) = info.stopped.write() {
+            *stopped = true;
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "watch {} stopped",
+            args.watch_id
+        ))]))
+    }
+    #[tool(
+        description = "将文本切分成约 512 词、重叠 64 词的分块并计算向量，存入内存向量库（并持久化到磁盘），用于后续 search_context 检索。"
+    )]
+    async fn index_document(
+        &self,
+        Parameters(args): Parameters<IndexDocumentArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let chunks = Self::chunk_text(&args.text);
+        let mut indexed = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            let (vector, embedding_source) = self.embed(&chunk).await?;
+            indexed.push(DocChunk {
+                uri: args.uri.clone(),
+                text: chunk,
+                vector,
+                embedding_source,
+            });
+        }
+
+        let count = indexed.len();
+        {
+            let vector_store = self.vector_store.clone();
+            let mut guard = vector_store.write().map_err(|err| {
+                McpError::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to acquired write lock :{}", err),
+                    None,
+                )
+            })?;
+            guard.extend(indexed);
+            Self::save_vector_store(&guard)?;
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "indexed {} chunks from {}",
+            count, args.uri
+        ))]))
+    }
+    /// 按空白切分为约 `CHUNK_SIZE_WORDS` 词的分块，相邻块重叠 `CHUNK_OVERLAP_WORDS` 词
+    fn chunk_text(text: &str) -> Vec<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        if words.is_empty() {
+            return vec![];
+        }
+        let step = CHUNK_SIZE_WORDS.saturating_sub(CHUNK_OVERLAP_WORDS).max(1);
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while start < words.len() {
+            let end = (start + CHUNK_SIZE_WORDS).min(words.len());
+            chunks.push(words[start..end].join(" "));
+            if end == words.len() {
+                break;
+            }
+            start += step;
+        }
+        chunks
+    }
+    /// 计算文本的向量表示，连同一个标识 backend 的来源标签一起返回。若设置了
+    /// `EMBEDDING_API_URL` 则通过共享 HTTP 客户端调用远程 embeddings 接口，
+    /// 否则退回到本地的确定性词袋哈希向量。来源标签用于在检索时识别并拒绝
+    /// 比较不同 backend（维度/语义都不兼容）产生的向量。
+    async fn embed(&self, text: &str) -> Result<(Vec<f32>, String), McpError> {
+        if let Ok(url) = std::env::var("EMBEDDING_API_URL") {
+            let response = self
+                .http_client
+                .post(&url)
+                .json(&serde_json::json!({ "input": text }))
+                .send()
+                .await
+                .map_err(|err| {
+                    McpError::new(
+                        ErrorCode::INTERNAL_ERROR,
+                        format!("embedding request failed: {}", err),
+                        None,
+                    )
+                })?;
+            let body: serde_json::Value = response.json().await.map_err(|err| {
+                McpError::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("embedding response was not json: {}", err),
+                    None,
+                )
+            })?;
+            let vector: Vec<f32> = body
+                .get("embedding")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+                .ok_or(McpError::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    "embedding response missing `embedding` array",
+                    None,
+                ))?;
+            let source = format!("remote:{}:{}", url, vector.len());
+            return Ok((vector, source));
+        }
+        let vector = Self::local_hash_embed(text);
+        let source = format!("local-hash:{}", vector.len());
+        Ok((vector, source))
+    }
+    /// 没有配置远程 embeddings 端点时使用的本地兜底：把词哈希到固定维度的
+    /// 桶里做词袋统计，再做 L2 归一化，得到一个可比较余弦相似度的向量。
+    fn local_hash_embed(text: &str) -> Vec<f32> {
+        const DIMS: usize = 256;
+        let mut vector = vec![0f32; DIMS];
+        for word in text.split_whitespace() {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            std::hash::Hash::hash(&word.to_lowercase(), &mut hasher);
+            let bucket = (std::hash::Hasher::finish(&hasher) as usize) % DIMS;
+            vector[bucket] += 1.0;
+        }
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for v in &mut vector {
+                *v /= norm;
+            }
+        }
+        vector
+    }
+    /// 返回 `None` 而不是用 `zip` 悄悄截断，避免把不同维度（来自不同 backend
+    /// 或不同版本的本地哈希）的向量比较出一个毫无意义但看起来正常的分数。
+    fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+        if a.len() != b.len() || a.is_empty() {
+            return None;
+        }
+        let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+        let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+        let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm_a == 0.0 || norm_b == 0.0 {
+            Some(0.0)
+        } else {
+            Some(dot / (norm_a * norm_b))
+        }
+    }
+    fn index_path() -> String {
+        std::env::var("INTERVIEW_INDEX_PATH").unwrap_or_else(|_| DEFAULT_INDEX_PATH.to_string())
+    }
+    fn load_vector_store() -> Vec<DocChunk> {
+        std::fs::read_to_string(Self::index_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+    fn save_vector_store(store: &[DocChunk]) -> Result<(), McpError> {
+        let content = serde_json::to_string(store).map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("failed to serialize vector store: {}", err),
+                None,
+            )
+        })?;
+        std::fs::write(Self::index_path(), content).map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("failed to persist vector store: {}", err),
+                None,
+            )
+        })
+    }
+    #[tool(
+        description = "将查询文本向量化，与已索引的分块做余弦相似度排序，返回最相关的 top-k 个分块及其来源 uri 和相似度分数。"
+    )]
+    async fn search_context(
+        &self,
+        Parameters(args): Parameters<SearchContextArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let (query_vector, query_source) = self.embed(&args.query).await?;
+        let top_k = args.top_k.unwrap_or(5);
+
+        let vector_store = self.vector_store.clone();
+        let guard = vector_store.read().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired read lock :{}", err),
+                None,
+            )
+        })?;
+
+        // Only compare chunks embedded by the same backend/dimension as the
+        // query; chunks from a different `EMBEDDING_API_URL` config or an
+        // older local-hash version are skipped rather than silently scored.
+        let skipped = guard.iter().filter(|c| c.embedding_source != query_source).count();
+        let mut scored: Vec<(f32, &DocChunk)> = guard
+            .iter()
+            .filter(|chunk| chunk.embedding_source == query_source)
+            .filter_map(|chunk| {
+                Self::cosine_similarity(&query_vector, &chunk.vector).map(|score| (score, chunk))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        if scored.is_empty() {
+            let message = if skipped > 0 {
+                format!(
+                    "no indexed documents matching embedding source `{}` ({} chunks from a different source were skipped)",
+                    query_source, skipped
+                )
+            } else {
+                "no indexed documents".to_string()
+            };
+            return Ok(CallToolResult::success(vec![Content::text(message)]));
+        }
+
+        let contents = scored
+            .into_iter()
+            .take(top_k)
+            .map(|(score, chunk)| {
+                Content::text(format!(
+                    "[{:.4}] ({}) {}",
+                    score, chunk.uri, chunk.text
+                ))
+            })
+            .collect();
+
+        Ok(CallToolResult::success(contents))
+    }
+    #[tool(
+        description = "加载一个 iCalendar (.ics) 日程文件，source 可以是绝对路径也可以是 http(s) URL，解析其中的 VEVENT 作为后续 next_session/session_remaining 的数据源。"
+    )]
+    async fn load_schedule(
+        &self,
+        Parameters(args): Parameters<LoadScheduleArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let content = if args.source.starts_with("http://") || args.source.starts_with("https://")
+        {
+            self.http_client
+                .get(&args.source)
+                .send()
+                .await
+                .map_err(|err| {
+                    McpError::new(
+                        ErrorCode::INVALID_REQUEST,
+                        format!("failed to fetch schedule :{}, error: {}", args.source, err),
+                        None,
+                    )
+                })?
+                .text()
+                .await
+                .map_err(|err| {
+                    McpError::new(
+                        ErrorCode::INVALID_REQUEST,
+                        format!("schedule response was not text: {}", err),
+                        None,
+                    )
+                })?
+        } else {
+            self.policy.check_path(&args.source).await?;
+            fs::read_to_string(&args.source).await.map_err(|err| {
+                McpError::new(
+                    ErrorCode::RESOURCE_NOT_FOUND,
+                    format!("file {} is not found, error: {}", args.source, err),
+                    None,
+                )
+            })?
+        };
+
+        let events = Self::parse_ics(&content);
+        let count = events.len();
+
+        let schedule_store = self.schedule_store.clone();
+        let mut guard = schedule_store.write().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired write lock :{}", err),
+                None,
+            )
+        })?;
+        *guard = events;
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "loaded {} events from {}",
+            count, args.source
+        ))]))
+    }
+    /// 解析 .ics 内容中的 VEVENT 块。按 RFC 5545 对折行做反折叠，只关心
+    /// UID/SUMMARY/DTSTART/DTEND/DURATION/RRULE，其余属性（如 ORGANIZER、
+    /// ATTENDEE）忽略。RRULE 只支持 FREQ=DAILY/WEEKLY 配合 INTERVAL/COUNT/UNTIL
+    /// 的基本展开（见 `expand_rrule`），更复杂的规则（如 BYDAY）当作单次事件处理。
+    fn parse_ics(content: &str) -> Vec<ScheduledEvent> {
+        let unfolded = Self::unfold_ics_lines(content);
+        let mut events = Vec::new();
+        let mut in_event = false;
+        let mut uid = String::new();
+        let mut summary = String::new();
+        let mut dtstart: Option<(DateTime<Local>, bool)> = None;
+        let mut dtend: Option<DateTime<Local>> = None;
+        let mut duration: Option<chrono::Duration> = None;
+        let mut rrule: Option<String> = None;
+
+        for line in unfolded {
+            if line == "BEGIN:VEVENT" {
+                in_event = true;
+                uid.clear();
+                summary.clear();
+                dtstart = None;
+                dtend = None;
+                duration = None;
+                rrule = None;
+                continue;
+            }
+            if line == "END:VEVENT" {
+                if let Some((start, all_day)) = dtstart {
+                    let end = dtend
+                        .or_else(|| duration.map(|d| start + d))
+                        .unwrap_or(if all_day {
+                            start + chrono::Duration::days(1)
+                        } else {
+                            start
+                        });
+                    match &rrule {
+                        Some(rule) => {
+                            events.extend(Self::expand_rrule(&uid, &summary, start, end, rule))
+                        }
+                        None => events.push(ScheduledEvent {
+                            uid: uid.clone(),
+                            summary: summary.clone(),
+                            start,
+                            end,
+                        }),
+                    }
+                }
+                in_event = false;
+                continue;
+            }
+            if !in_event {
+                continue;
+            }
+            let Some((name_and_params, value)) = line.split_once(':') else {
+                continue;
+            };
+            let mut parts = name_and_params.split(';');
+            let name = parts.next().unwrap_or_default();
+            let params: HashMap<String, String> = parts
+                .filter_map(|p| p.split_once('='))
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+
+            match name {
+                "UID" => uid = value.to_string(),
+                "SUMMARY" => summary = value.to_string(),
+                "DTSTART" => dtstart = Self::parse_ics_datetime(value, &params),
+                "DTEND" => dtend = Self::parse_ics_datetime(value, &params).map(|(dt, _)| dt),
+                "DURATION" => duration = Self::parse_ics_duration(value),
+                "RRULE" => rrule = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        events
+    }
+    /// 展开一个带 RRULE 的 VEVENT 的重复发生实例。只实现 RFC 5545 的基本子集：
+    /// `FREQ=DAILY`/`FREQ=WEEKLY` 配合可选的 `INTERVAL`（默认 1）、`COUNT`
+    /// 或 `UNTIL`。不认识的 FREQ 值（MONTHLY/YEARLY 等）当作单次事件，
+    /// 不做展开；带 BYDAY 等 `BY*` 部分的规则同样当作单次事件。展开数量始终封顶在
+    /// `RRULE_MAX_OCCURRENCES`，避免一个没有 COUNT/UNTIL 的规则生成无穷多个事件。
+    fn expand_rrule(
+        uid: &str,
+        summary: &str,
+        start: DateTime<Local>,
+        end: DateTime<Local>,
+        rrule: &str,
+    ) -> Vec<ScheduledEvent> {
+        let params: HashMap<&str, &str> = rrule.split(';').filter_map(|p| p.split_once('=')).collect();
+        let has_by_rule = params.keys().any(|k| k.starts_with("BY"));
+        let step = match params.get("FREQ").copied() {
+            Some("DAILY") if !has_by_rule => {
+                let interval: i64 = params.get("INTERVAL").and_then(|v| v.parse().ok()).unwrap_or(1);
+                chrono::Duration::days(interval.max(1))
+            }
+            Some("WEEKLY") if !has_by_rule => {
+                let interval: i64 = params.get("INTERVAL").and_then(|v| v.parse().ok()).unwrap_or(1);
+                chrono::Duration::weeks(interval.max(1))
+            }
+            _ => {
+                return vec![ScheduledEvent {
+                    uid: uid.to_string(),
+                    summary: summary.to_string(),
+                    start,
+                    end,
+                }];
+            }
+        };
+
+        let count = params.get("COUNT").and_then(|v| v.parse::<usize>().ok());
+        let until = params
+            .get("UNTIL")
+            .and_then(|v| Self::parse_ics_datetime(v, &HashMap::new()))
+            .map(|(dt, _)| dt);
+        let limit = count.unwrap_or(RRULE_MAX_OCCURRENCES).min(RRULE_MAX_OCCURRENCES);
+
+        let mut events = Vec::new();
+        let mut occurrence_start = start;
+        let mut occurrence_end = end;
+        for n in 0..limit {
+            if let Some(until) = until {
+                if occurrence_start > until {
+                    break;
+                }
+            }
+            events.push(ScheduledEvent {
+                uid: if n == 0 { uid.to_string() } else { format!("{}#{}", uid, n) },
+                summary: summary.to_string(),
+                start: occurrence_start,
+                end: occurrence_end,
+            });
+            occurrence_start += step;
+            occurrence_end += step;
+        }
+        events
+    }
+    /// 反折叠 .ics 行：以空格或制表符开头的行是上一行的延续
+    fn unfold_ics_lines(content: &str) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        for raw in content.lines() {
+            if (raw.starts_with(' ') || raw.starts_with('\t')) && !lines.is_empty() {
+                let last = lines.last_mut().unwrap();
+                // RFC 5545: only the single leading space/tab is part of the fold
+                last.push_str(&raw[1..]);
+            } else {
+                lines.push(raw.trim_end_matches('\r').to_string());
+            }
+        }
+        lines
+    }
+    /// 解析 DTSTART/DTEND 的值。支持 `...Z`（UTC）、`TZID=...`（通过 chrono-tz
+    /// 解析为具体时区换算到本地时间）以及 `VALUE=DATE`（全天事件，按本地时区处理）。
+    /// `TZID` 不在 IANA 时区数据库中时返回 `None`，而不是静默按本地时区重新解释，
+    /// 因为那样会悄悄产出错误的面试时间。
+    fn parse_ics_datetime(
+        value: &str,
+        params: &HashMap<String, String>,
+    ) -> Option<(DateTime<Local>, bool)> {
+        if params.get("VALUE").map(String::as_str) == Some("DATE") || !value.contains('T') {
+            let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+            let naive = date.and_hms_opt(0, 0, 0)?;
+            return Some((Local.from_local_datetime(&naive).single()?, true));
+        }
+        if let Some(stripped) = value.strip_suffix('Z') {
+            let naive = NaiveDateTime::parse_from_str(stripped, "%Y%m%dT%H%M%S").ok()?;
+            let utc = Utc.from_utc_datetime(&naive);
+            return Some((utc.with_timezone(&Local), false));
+        }
+        if let Some(tzid) = params.get("TZID") {
+            let tz: Tz = tzid.parse().ok()?;
+            let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+            let zoned = tz.from_local_datetime(&naive).single()?;
+            return Some((zoned.with_timezone(&Local), false));
+        }
+        let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+        Some((Local.from_local_datetime(&naive).single()?, false))
+    }
+    /// 解析 ISO-8601 DURATION，如 `PT1H30M`、`P1DT2H`
+    fn parse_ics_duration(value: &str) -> Option<chrono::Duration> {
+        let value = value.strip_prefix('P')?;
+        let (date_part, time_part) = value.split_once('T').unwrap_or((value, ""));
+        let mut total = chrono::Duration::zero();
+
+        let mut num = String::new();
+        for ch in date_part.chars() {
+            if ch.is_ascii_digit() {
+                num.push(ch);
+            } else if ch == 'D' {
+                total += chrono::Duration::days(num.parse().unwrap_or(0));
+                num.clear();
+            } else if ch == 'W' {
+                total += chrono::Duration::weeks(num.parse().unwrap_or(0));
+                num.clear();
+            }
+        }
+        num.clear();
+        for ch in time_part.chars() {
+            if ch.is_ascii_digit() {
+                num.push(ch);
+            } else if ch == 'H' {
+                total += chrono::Duration::hours(num.parse().unwrap_or(0));
+                num.clear();
+            } else if ch == 'M' {
+                total += chrono::Duration::minutes(num.parse().unwrap_or(0));
+                num.clear();
+            } else if ch == 'S' {
+                total += chrono::Duration::seconds(num.parse().unwrap_or(0));
+                num.clear();
+            }
+        }
+        Some(total)
+    }
+    #[tool(
+        description = "返回日程中下一个尚未开始的面试场次（summary）以及距其开始还有多少秒，使用 chrono::Local 计算。"
+    )]
+    async fn next_session(&self) -> Result<CallToolResult, McpError> {
+        let now = Local::now();
+        let schedule_store = self.schedule_store.clone();
+        let guard = schedule_store.read().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired read lock :{}", err),
+                None,
+            )
+        })?;
+        let next = guard
+            .iter()
+            .filter(|e| e.start > now)
+            .min_by_key(|e| e.start);
+
+        match next {
+            Some(event) => {
+                let seconds = (event.start - now).num_seconds();
+                Ok(CallToolResult::success(vec![
+                    Content::text(format!("uid: {}", event.uid)),
+                    Content::text(format!("summary: {}", event.summary)),
+                    Content::text(format!("seconds_until_start: {}", seconds)),
+                ]))
+            }
+            None => Ok(CallToolResult::success(vec![Content::text(
+                "no upcoming session",
+            )])),
+        }
+    }
+    #[tool(
+        description = "如果当前处于某个日程场次中，返回该场次距 DTEND 还剩多少秒。"
+    )]
+    async fn session_remaining(&self) -> Result<CallToolResult, McpError> {
+        let now = Local::now();
+        let schedule_store = self.schedule_store.clone();
+        let guard = schedule_store.read().map_err(|err| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to acquired read lock :{}", err),
+                None,
+            )
+        })?;
+        let current = guard.iter().find(|e| e.start <= now && now <= e.end);
+
+        match current {
+            Some(event) => {
+                let seconds = (event.end - now).num_seconds();
+                Ok(CallToolResult::success(vec![
+                    Content::text(format!("uid: {}", event.uid)),
+                    Content::text(format!("summary: {}", event.summary)),
+                    Content::text(format!("seconds_remaining: {}", seconds)),
+                ]))
+            }
+            None => Ok(CallToolResult::success(vec![Content::text(
+                "no session in progress",
+            )])),
+        }
+    }
+    #[tool(
+        description = "通过共享 HTTP 客户端访问 url，支持 method/body/headers，自带超时和瞬时错误重试，返回状态码与截断后的响应内容"
+    )]
+    async fn get_url(
+        &self,
+        Parameters(args): Parameters<GetUrlArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let method = args
+            .method
+            .as_deref()
+            .unwrap_or("GET")
+            .parse::<reqwest::Method>()
+            .map_err(|err| {
+                McpError::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("invalid http method, error: {}", err),
+                    None,
+                )
+            })?;
+
+        let mut last_err = None;
+        let mut retries = 0;
+        for attempt in 0..=DEFAULT_MAX_RETRIES {
+            let mut request = self.http_client.request(method.clone(), &args.url);
+            if let Some(headers) = &args.headers {
+                for (key, value) in headers {
+                    request = request.header(key, value);
+                }
+            }
+            if let Some(body) = args.body.clone() {
+                request = request.body(body);
+            }
+
+            match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_server_error() && attempt < DEFAULT_MAX_RETRIES {
+                        last_err = Some(format!("server error: {}", status));
+                        time::sleep(Duration::from_millis(200 * 2u64.pow(attempt))).await;
+                        continue;
+                    }
+                    let text = response.text().await.map_err(|_| {
+                        McpError::new(
+                            ErrorCode::INVALID_REQUEST,
+                            "the response is not String",
+                            None,
+                        )
+                    })?;
+                    let truncated = Self::truncate_response_text(text);
+                    return Ok(CallToolResult::success(vec![
+                        Content::text(format!("status: {}", status.as_u16())),
+                        Content::text(truncated),
+                    ]));
+                }
+                Err(err) => {
+                    last_err = Some(err.to_string());
+                    if attempt < DEFAULT_MAX_RETRIES && (err.is_connect() || err.is_timeout()) {
+                        time::sleep(Duration::from_millis(200 * 2u64.pow(attempt))).await;
+                        continue;
+                    }
+                    retries = attempt;
+                    break;
+                }
+            }
+        }
+
+        Err(McpError::new(
+            ErrorCode::INVALID_REQUEST,
+            format!(
+                "Failed to request url :{} after {} retries, last error: {}",
+                args.url,
+                retries,
+                last_err.unwrap_or_default()
+            ),
+            None,
+        ))
+    }
+    /// 将响应正文截断到 `DEFAULT_MAX_RESPONSE_BYTES` 字节以内，截断点回退到
+    /// 最近的字符边界，避免在多字节 UTF-8 字符中间切片导致 panic。
+    fn truncate_response_text(text: String) -> String {
+        if text.len() <= DEFAULT_MAX_RESPONSE_BYTES {
+            return text;
+        }
+        let mut boundary = DEFAULT_MAX_RESPONSE_BYTES;
+        while boundary > 0 && !text.is_char_boundary(boundary) {
+            boundary -= 1;
+        }
+        format!(
+            "{}...[truncated {} bytes]",
+            &text[..boundary],
+            text.len() - boundary
+        )
     }
 }
 
@@ -304,11 +1758,23 @@ impl InterviewTool {
 #[prompt_handler]
 impl ServerHandler for InterviewTool {
     fn get_info(&self) -> ServerInfo {
+        let roots_desc = if self.policy.allowed_roots.is_empty() {
+            "unrestricted".to_string()
+        } else {
+            self.policy
+                .allowed_roots
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
         ServerInfo {
-            instructions: Some(
-                "Support tool for recording moments, ideal for time-limited interviews and tests"
-                    .into(),
-            ),
+            instructions: Some(format!(
+                "Support tool for recording moments, ideal for time-limited interviews and tests. \
+                 Sandbox policy: allowed path roots = [{}], shell mode = {}.",
+                roots_desc,
+                self.policy.shell_mode.describe()
+            )),
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
                 .enable_prompts()
@@ -321,11 +1787,22 @@ impl ServerHandler for InterviewTool {
         _request: Option<PaginatedRequestParam>,
         _: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, McpError> {
+        let mut resources = vec![
+            self._create_resource_text("str:////Users/to/some/path/", "cwd"),
+            self._create_resource_text("memo://insights", "memo-name"),
+        ];
+
+        if let Ok(guard) = self.vector_store.read() {
+            let mut seen = std::collections::HashSet::new();
+            for chunk in guard.iter() {
+                if seen.insert(chunk.uri.clone()) {
+                    resources.push(self._create_resource_text(&chunk.uri, &chunk.uri));
+                }
+            }
+        }
+
         Ok(ListResourcesResult {
-            resources: vec![
-                self._create_resource_text("str:////Users/to/some/path/", "cwd"),
-                self._create_resource_text("memo://insights", "memo-name"),
-            ],
+            resources,
             next_cursor: None,
         })
     }
@@ -353,4 +1830,236 @@ impl ServerHandler for InterviewTool {
 //         println!("home dir {}",home_dir.to_str().unwrap());
 //         Ok(())
 //     } 
-// }
\ No newline at end of file
+// }
+#[cfg(test)]
+mod interview_tool_tests {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn parse_ics_duration_parses_hours_and_minutes() {
+        let d = InterviewTool::parse_ics_duration("PT1H30M").unwrap();
+        assert_eq!(d, chrono::Duration::minutes(90));
+    }
+
+    #[test]
+    fn parse_ics_duration_parses_days_and_time() {
+        let d = InterviewTool::parse_ics_duration("P1DT2H").unwrap();
+        assert_eq!(d, chrono::Duration::hours(26));
+    }
+
+    #[test]
+    fn parse_ics_extracts_single_event() {
+        let ics = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:abc-1\r\nSUMMARY:Interview\r\nDTSTART:20260101T090000Z\r\nDTEND:20260101T100000Z\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let events = InterviewTool::parse_ics(ics);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].uid, "abc-1");
+        assert_eq!(events[0].summary, "Interview");
+        assert!(events[0].end > events[0].start);
+    }
+
+    #[test]
+    fn parse_ics_resolves_tzid_to_correct_instant() {
+        let ics = "BEGIN:VEVENT\r\nUID:tz-1\r\nSUMMARY:Interview\r\nDTSTART;TZID=America/New_York:20260101T090000\r\nDTEND;TZID=America/New_York:20260101T100000\r\nEND:VEVENT\r\n";
+        let events = InterviewTool::parse_ics(ics);
+        assert_eq!(events.len(), 1);
+        // 2026-01-01 09:00 America/New_York (UTC-5 in January) == 14:00 UTC
+        assert_eq!(events[0].start.with_timezone(&Utc).hour(), 14);
+    }
+
+    #[test]
+    fn parse_ics_skips_event_with_unknown_tzid() {
+        let ics = "BEGIN:VEVENT\r\nUID:tz-bad\r\nSUMMARY:Interview\r\nDTSTART;TZID=Not/A_Zone:20260101T090000\r\nDTEND;TZID=Not/A_Zone:20260101T100000\r\nEND:VEVENT\r\n";
+        let events = InterviewTool::parse_ics(ics);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn parse_ics_expands_weekly_rrule_with_count() {
+        let ics = "BEGIN:VEVENT\r\nUID:weekly-1\r\nSUMMARY:Standup\r\nDTSTART:20260101T090000Z\r\nDTEND:20260101T093000Z\r\nRRULE:FREQ=WEEKLY;COUNT=3\r\nEND:VEVENT\r\n";
+        let events = InterviewTool::parse_ics(ics);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1].start - events[0].start, chrono::Duration::weeks(1));
+        assert_eq!(events[2].start - events[0].start, chrono::Duration::weeks(2));
+    }
+
+    #[test]
+    fn parse_ics_unrecognized_freq_is_not_expanded() {
+        let ics = "BEGIN:VEVENT\r\nUID:monthly-1\r\nSUMMARY:Review\r\nDTSTART:20260101T090000Z\r\nDTEND:20260101T093000Z\r\nRRULE:FREQ=MONTHLY;COUNT=3\r\nEND:VEVENT\r\n";
+        let events = InterviewTool::parse_ics(ics);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn chunk_text_splits_with_overlap() {
+        let words: Vec<String> = (0..600).map(|n| n.to_string()).collect();
+        let text = words.join(" ");
+        let chunks = InterviewTool::chunk_text(&text);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].split_whitespace().count(), 512);
+        assert_eq!(chunks[0].split_whitespace().last().unwrap(), "511");
+        assert_eq!(chunks[1].split_whitespace().next().unwrap(), "448");
+    }
+
+    #[test]
+    fn chunk_text_empty_input_yields_no_chunks() {
+        assert!(InterviewTool::chunk_text("   ").is_empty());
+    }
+
+    #[test]
+    fn truncate_response_text_passes_through_short_text() {
+        let text = "hello world".to_string();
+        assert_eq!(InterviewTool::truncate_response_text(text.clone()), text);
+    }
+
+    #[test]
+    fn truncate_response_text_cuts_on_a_char_boundary() {
+        // A multi-byte character straddles the truncation boundary: the cut
+        // point must walk back to the nearest char boundary, not panic.
+        let mut text = "a".repeat(DEFAULT_MAX_RESPONSE_BYTES - 1);
+        text.push('喵'); // 3-byte UTF-8 character straddling the cutoff
+        text.push_str(&"b".repeat(10));
+
+        let truncated = InterviewTool::truncate_response_text(text);
+
+        // The cut walks back one byte, then the 23-byte marker is appended.
+        assert_eq!(
+            truncated.len(),
+            DEFAULT_MAX_RESPONSE_BYTES - 1 + "...[truncated 13 bytes]".len()
+        );
+        assert!(truncated.contains("[truncated"));
+        assert!(truncated.is_char_boundary(truncated.find("...").unwrap()));
+    }
+
+    #[tokio::test]
+    async fn get_url_exhausts_retries_on_unreachable_host() {
+        let tool = InterviewTool::new();
+        let args = Parameters(GetUrlArgs {
+            url: "http://127.0.0.1:1/".to_string(),
+            method: None,
+            headers: None,
+            body: None,
+        });
+
+        let result = tool.get_url(args).await;
+
+        let err = result.expect_err("connecting to a closed local port should fail");
+        let rendered = format!("{:?}", err);
+        assert!(rendered.contains(&format!("after {} retries", DEFAULT_MAX_RETRIES)));
+    }
+
+    #[test]
+    fn ring_push_evicts_oldest_bytes_past_capacity() {
+        let mut buf = "a".repeat(PROCESS_OUTPUT_RING_CAPACITY);
+        ring_push(&mut buf, "bbb");
+        assert_eq!(buf.len(), PROCESS_OUTPUT_RING_CAPACITY);
+        assert!(buf.ends_with("bbb"));
+    }
+
+    #[test]
+    fn ring_push_trims_on_a_char_boundary() {
+        // A multi-byte character sits right at the front, straddling the byte
+        // offset the naive `excess_start` cut would land on: the trim must walk
+        // forward to the next char boundary instead of slicing mid-codepoint.
+        let mut buf = format!("喵{}", "a".repeat(PROCESS_OUTPUT_RING_CAPACITY - 1));
+        assert_eq!(buf.len(), PROCESS_OUTPUT_RING_CAPACITY + 2);
+
+        ring_push(&mut buf, "");
+
+        // The straddled multi-byte char must be fully evicted, not split.
+        assert!(!buf.contains('喵'));
+        assert!(buf.len() <= PROCESS_OUTPUT_RING_CAPACITY);
+        assert!(buf.chars().all(|c| c == 'a'));
+    }
+
+    #[test]
+    fn debounce_window_exceeds_poll_interval() {
+        assert!(WATCH_DEBOUNCE_WINDOW > WATCH_POLL_INTERVAL);
+    }
+
+    #[test]
+    fn debounce_suppresses_modify_within_window_across_ticks() {
+        let first_tick = Instant::now();
+        let second_tick = first_tick + WATCH_POLL_INTERVAL;
+        let third_tick = first_tick + WATCH_DEBOUNCE_WINDOW + WATCH_POLL_INTERVAL;
+
+        assert!(!InterviewTool::is_within_debounce_window(None, first_tick));
+        // One poll interval after the first emission: still inside the debounce
+        // window, so a repeated modify at this tick must be suppressed.
+        assert!(InterviewTool::is_within_debounce_window(
+            Some(first_tick),
+            second_tick
+        ));
+        // Far enough past the debounce window: the modify should be emitted again.
+        assert!(!InterviewTool::is_within_debounce_window(
+            Some(first_tick),
+            third_tick
+        ));
+    }
+
+    #[tokio::test]
+    async fn check_path_allows_everything_when_unconfigured() {
+        let policy = SecurityPolicy {
+            allowed_roots: vec![],
+            shell_mode: ShellMode::Unrestricted,
+            arg_validator: None,
+        };
+        assert!(policy.check_path("/etc/passwd").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_path_rejects_traversal_outside_allowed_root() {
+        let tmp = std::env::temp_dir().join(format!(
+            "interview_tool_test_{}",
+            std::process::id()
+        ));
+        let allowed_root = tmp.join("allowed");
+        let outside_dir = tmp.join("outside");
+        std::fs::create_dir_all(&allowed_root).unwrap();
+        std::fs::create_dir_all(&outside_dir).unwrap();
+        let outside_file = outside_dir.join("secret.txt");
+        std::fs::write(&outside_file, b"secret").unwrap();
+
+        let policy = SecurityPolicy {
+            allowed_roots: vec![std::fs::canonicalize(&allowed_root).unwrap()],
+            shell_mode: ShellMode::Unrestricted,
+            arg_validator: None,
+        };
+
+        let traversal = allowed_root.join("..").join("outside").join("secret.txt");
+        assert!(policy.check_path(traversal.to_str().unwrap()).await.is_err());
+        assert!(policy
+            .check_path(allowed_root.join("fine.txt").to_str().unwrap())
+            .await
+            .is_ok());
+
+        std::fs::remove_dir_all(&tmp).unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn check_path_rejects_symlink_escaping_allowed_root() {
+        let tmp = std::env::temp_dir().join(format!(
+            "interview_tool_test_symlink_{}",
+            std::process::id()
+        ));
+        let allowed_root = tmp.join("allowed");
+        let outside_dir = tmp.join("outside");
+        std::fs::create_dir_all(&allowed_root).unwrap();
+        std::fs::create_dir_all(&outside_dir).unwrap();
+        let outside_file = outside_dir.join("secret.txt");
+        std::fs::write(&outside_file, b"secret").unwrap();
+        let link = allowed_root.join("escape.txt");
+        std::os::unix::fs::symlink(&outside_file, &link).unwrap();
+
+        let policy = SecurityPolicy {
+            allowed_roots: vec![std::fs::canonicalize(&allowed_root).unwrap()],
+            shell_mode: ShellMode::Unrestricted,
+            arg_validator: None,
+        };
+
+        assert!(policy.check_path(link.to_str().unwrap()).await.is_err());
+
+        std::fs::remove_dir_all(&tmp).unwrap();
+    }
+}